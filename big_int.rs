@@ -0,0 +1,131 @@
+//! Prisma's `BigInt` scalar.
+//!
+//! the query engine takes and returns `BigInt` values as strings, so they don't lose precision in
+//! json. [`BigInt`] does the (de)serialization, types that can't be wrapped can use the functions
+//! in this module instead, `#[serde(with = "prisma_client::big_int")]`.
+use serde::{
+	de::{self, Visitor},
+	Deserializer, Serializer,
+};
+use std::{convert::TryFrom, fmt, ops::Deref};
+
+/// A `BigInt` column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BigInt(pub i64);
+
+impl BigInt {
+	/// Returns the integer.
+	pub fn into_inner(self) -> i64 {
+		self.0
+	}
+}
+
+impl Deref for BigInt {
+	type Target = i64;
+
+	fn deref(&self) -> &i64 {
+		&self.0
+	}
+}
+
+impl fmt::Display for BigInt {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl From<i64> for BigInt {
+	fn from(value: i64) -> Self {
+		BigInt(value)
+	}
+}
+
+impl From<i32> for BigInt {
+	fn from(value: i32) -> Self {
+		BigInt(value.into())
+	}
+}
+
+impl From<BigInt> for i64 {
+	fn from(value: BigInt) -> Self {
+		value.0
+	}
+}
+
+impl serde::Serialize for BigInt {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for BigInt {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(BigInt)
+	}
+}
+
+/// Serializes `value` as a string.
+pub fn serialize<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.collect_str(value)
+}
+
+/// Deserializes a string, or a number.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+	deserializer.deserialize_any(BigIntVisitor)
+}
+
+struct BigIntVisitor;
+
+impl<'de> Visitor<'de> for BigIntVisitor {
+	type Value = i64;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("a 64 bit integer, or a string of one")
+	}
+
+	fn visit_i64<E: de::Error>(self, value: i64) -> Result<i64, E> {
+		Ok(value)
+	}
+
+	fn visit_u64<E: de::Error>(self, value: u64) -> Result<i64, E> {
+		i64::try_from(value).map_err(E::custom)
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<i64, E> {
+		value.parse().map_err(E::custom)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn serializes_as_a_string() {
+		assert_eq!(serde_json::to_value(BigInt(0)).unwrap(), json!("0"));
+		assert_eq!(serde_json::to_value(BigInt(i64::MAX)).unwrap(), json!("9223372036854775807"));
+		assert_eq!(serde_json::to_value(BigInt(i64::MIN)).unwrap(), json!("-9223372036854775808"));
+	}
+
+	#[test]
+	fn deserializes_strings_and_numbers() {
+		for value in [i64::MIN, -1, 0, i64::MAX] {
+			let string = serde_json::to_value(BigInt(value)).unwrap();
+			assert_eq!(serde_json::from_value::<BigInt>(string).unwrap(), BigInt(value));
+			assert_eq!(serde_json::from_value::<BigInt>(json!(value)).unwrap(), BigInt(value));
+		}
+	}
+
+	#[test]
+	fn rejects_out_of_range_and_non_numeric_values() {
+		let big_int = |value| serde_json::from_value::<BigInt>(value);
+		assert!(big_int(json!("9223372036854775808")).is_err());
+		assert!(big_int(json!("-9223372036854775809")).is_err());
+		assert!(big_int(json!(u64::MAX)).is_err());
+		assert!(big_int(json!("12a")).is_err());
+		assert!(big_int(json!("")).is_err());
+		assert!(big_int(json!("1.5")).is_err());
+		assert!(big_int(json!(1.5)).is_err());
+	}
+}
@@ -0,0 +1,162 @@
+//! Prisma's `Bytes` scalar.
+//!
+//! the query engine takes and returns `Bytes` values as base64 strings, [`Bytes`] does the
+//! (de)serialization so binary columns can be written and read as plain bytes.
+//!
+//! ```rust
+//! # use prisma_client::{Bytes, Query};
+//! # use serde::Deserialize;
+//! #[derive(Query, Deserialize)]
+//! struct Avatar {
+//!     id: i64,
+//!     image: Bytes,
+//! }
+//! ```
+//!
+//! Types that can't be wrapped can use the functions in this module instead,
+//! `#[serde(with = "prisma_client::bytes")]`.
+use serde::{
+	de::{self, SeqAccess, Visitor},
+	Deserializer, Serializer,
+};
+use std::{
+	fmt,
+	ops::{Deref, DerefMut},
+};
+
+/// A `Bytes` column.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+	/// Returns the bytes.
+	pub fn into_inner(self) -> Vec<u8> {
+		self.0
+	}
+}
+
+impl Deref for Bytes {
+	type Target = Vec<u8>;
+
+	fn deref(&self) -> &Vec<u8> {
+		&self.0
+	}
+}
+
+impl DerefMut for Bytes {
+	fn deref_mut(&mut self) -> &mut Vec<u8> {
+		&mut self.0
+	}
+}
+
+impl From<Vec<u8>> for Bytes {
+	fn from(bytes: Vec<u8>) -> Self {
+		Bytes(bytes)
+	}
+}
+
+impl From<&[u8]> for Bytes {
+	fn from(bytes: &[u8]) -> Self {
+		Bytes(bytes.to_vec())
+	}
+}
+
+impl serde::Serialize for Bytes {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for Bytes {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Bytes)
+	}
+}
+
+/// Serializes `bytes` as a base64 string.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: AsRef<[u8]> + ?Sized,
+	S: Serializer,
+{
+	serializer.serialize_str(&base64::encode(bytes))
+}
+
+/// Deserializes a base64 string, or raw bytes.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_any(BytesVisitor)
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+	type Value = Vec<u8>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("a base64 string")
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<Vec<u8>, E> {
+		base64::decode(value).map_err(E::custom)
+	}
+
+	fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+		Ok(value.to_vec())
+	}
+
+	fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+		Ok(value)
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+		let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+		while let Some(byte) = seq.next_element()? {
+			bytes.push(byte);
+		}
+		Ok(bytes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn serializes_as_base64() {
+		let encode = |bytes: &[u8]| serde_json::to_value(Bytes::from(bytes)).unwrap();
+		assert_eq!(encode(b""), json!(""));
+		assert_eq!(encode(b"abc"), json!("YWJj"));
+		// padded to a multiple of 4.
+		assert_eq!(encode(&[0xff]), json!("/w=="));
+		assert_eq!(encode(&[0xff, 0xfe]), json!("//4="));
+	}
+
+	#[test]
+	fn deserializes_base64() {
+		for bytes in [&b""[..], b"abc", &[0xff], &[0xff, 0xfe], &[0, 1, 254, 255]] {
+			let value = serde_json::to_value(Bytes::from(bytes)).unwrap();
+			assert_eq!(serde_json::from_value::<Bytes>(value).unwrap().into_inner(), bytes);
+		}
+		// the padding can be left out.
+		let unpadded: Bytes = serde_json::from_value(json!("/w")).unwrap();
+		assert_eq!(unpadded, Bytes(vec![0xff]));
+		// raw bytes, e.g a json array of them.
+		let bytes: Bytes = serde_json::from_value(json!([0, 255])).unwrap();
+		assert_eq!(bytes, Bytes(vec![0, 255]));
+	}
+
+	#[test]
+	fn rejects_invalid_base64() {
+		let bytes = |value| serde_json::from_value::<Bytes>(value);
+		assert!(bytes(json!("YWJj!")).is_err());
+		assert!(bytes(json!("Y")).is_err());
+		// too much padding, and a last symbol with bits past the last byte.
+		assert!(bytes(json!("/w===")).is_err());
+		assert!(bytes(json!("/x==")).is_err());
+		assert!(bytes(json!(1)).is_err());
+	}
+}
@@ -1,59 +1,33 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, Attribute, DeriveInput, Lit, Meta, MetaList, NestedMeta};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+	parse_macro_input, parse_quote, Attribute, Data, DataStruct, DeriveInput, Field, Fields,
+	GenericParam, Lit, LitStr, Meta, NestedMeta, Path,
+};
+
+/// The rules `rename_all` accepts, the same ones serde accepts.
+const RENAME_RULES: &[&str] = &[
+	"lowercase",
+	"UPPERCASE",
+	"PascalCase",
+	"camelCase",
+	"snake_case",
+	"SCREAMING_SNAKE_CASE",
+	"kebab-case",
+	"SCREAMING-KEBAB-CASE",
+];
 
 /// Derives a Queryable trait for a struct/enum
 #[proc_macro_derive(Query, attributes(query))]
 pub fn my_macro(input: TokenStream) -> TokenStream {
 	// Parse the input tokens into a syntax tree
 	let input = parse_macro_input!(input as DeriveInput);
-	let name = input.ident;
-	let (impl_gen, type_gen, where_clause) = input.generics.split_for_impl();
-
-	let m = match input.data {
-		syn::Data::Struct(s) => s,
-		syn::Data::Enum(_) => {
-			let expanded = quote! {
-				impl #impl_gen prisma_client::Queryable for #name #type_gen #where_clause {
-					fn query() -> String {
-						String::new()
-					}
-				}
-			};
-
-			// Hand the output tokens back to the compiler
-			return TokenStream::from(expanded)
-		},
-		_ => unreachable!(),
-	};
-	// todo: validate against remote struct.
-	let fields = m
-		.fields
-		.iter()
-		.map(|f| {
-			let rename = get_rename(&f.attrs);
-			let (name, ty) =
-				(rename.or_else(|| Some(format!("{}", f.ident.as_ref().unwrap()))).unwrap(), &f.ty);
-			quote! {
-				query.push_str(&format!("{} {} ", #name, <#ty as prisma_client::Queryable>::query()));
-			}
-		})
-		.collect::<Vec<_>>();
-
-	let expanded = quote! {
-		impl #impl_gen prisma_client::Queryable for #name #type_gen #where_clause {
-			fn query() -> String {
-				let mut query = String::new();
-				#(#fields)*
-				format!("{{ {}}}", query)
-			}
-		}
-	};
-
-	// Hand the output tokens back to the compiler
-	TokenStream::from(expanded)
+	expand(input, quote!(prisma_client::Queryable))
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
 }
 
 /// to be used internally by the prisma lib.
@@ -61,71 +35,504 @@ pub fn my_macro(input: TokenStream) -> TokenStream {
 pub fn my_macro2(input: TokenStream) -> TokenStream {
 	// Parse the input tokens into a syntax tree
 	let input = parse_macro_input!(input as DeriveInput);
-	let name = input.ident;
-	let (impl_gen, type_gen, where_clause) = input.generics.split_for_impl();
-
-	let m = match input.data {
-		syn::Data::Struct(s) => s,
-		syn::Data::Enum(_) => {
-			let expanded = quote! {
-				impl #impl_gen Queryable for #name #type_gen #where_clause {
-					fn query() -> String {
-						String::new()
-					}
-				}
+	expand(input, quote!(Queryable))
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
+}
+
+/// Implements `queryable`, the path to the `Queryable` trait, for a struct/enum.
+fn expand(input: DeriveInput, queryable: TokenStream2) -> syn::Result<TokenStream2> {
+	let name = &input.ident;
+	let mut generics = input.generics.clone();
+	let container = ContainerAttrs::parse(&input.attrs)?;
+
+	let query = match &input.data {
+		// enums are scalars, there's nothing to select.
+		Data::Enum(_) => quote!(""),
+		Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) => {
+			// todo: validate against remote struct.
+			let rename_all = container.rename_all(&input.attrs)?;
+			let serde_default = serde_flag(&input.attrs, &["default"]);
+			let fields = fields
+				.named
+				.iter()
+				.map(|f| {
+					let attrs = FieldAttrs::parse(&f.attrs, serde_default)?;
+					let (name, ty) = (field_name(f, &attrs, rename_all.as_deref())?, &f.ty);
+					Ok(if attrs.skip {
+						quote!()
+					} else if attrs.flatten {
+						quote! {
+							let inner = <#ty as #queryable>::query().trim();
+							let inner = inner.strip_prefix('{').and_then(|i| i.strip_suffix('}'));
+							query.push_str(inner.map_or("", str::trim));
+							query.push(' ');
+						}
+					} else {
+						quote! {
+							query.push_str(&format!("{} {} ", #name, <#ty as #queryable>::query()));
+						}
+					})
+				})
+				.collect::<syn::Result<Vec<_>>>()?;
+
+			let build = quote! {
+				let mut query = String::new();
+				#(#fields)*
+				format!("{{ {}}}", query)
 			};
 
-			// Hand the output tokens back to the compiler
-			return TokenStream::from(expanded)
+			// a `static` in a generic impl is shared by all its instances, those are cached by
+			// type, which needs the type parameters to be `'static`. Lifetimes don't change the
+			// selection.
+			if generics.params.iter().all(|param| matches!(param, GenericParam::Lifetime(_))) {
+				quote! {
+					static QUERY: prisma_client::once_cell::sync::OnceCell<String> =
+						prisma_client::once_cell::sync::OnceCell::new();
+					QUERY.get_or_init(|| { #build })
+				}
+			} else {
+				let params = generics.params.iter().map(|param| match param {
+					GenericParam::Lifetime(_) => quote!('static),
+					GenericParam::Type(ty) => ty.ident.to_token_stream(),
+					GenericParam::Const(konst) => konst.ident.to_token_stream(),
+				});
+				let key = quote!(#name<#(#params),*>);
+				for param in generics.type_params_mut() {
+					param.bounds.push(parse_quote!('static));
+				}
+				quote!(prisma_client::cached_query::<#key>(|| { #build }))
+			}
 		},
-		_ => unreachable!(),
+		// a newtype selects whatever the type it wraps selects.
+		Data::Struct(DataStruct { fields: Fields::Unnamed(fields), .. })
+			if fields.unnamed.len() == 1 =>
+		{
+			let field = &fields.unnamed[0];
+			let attrs = FieldAttrs::parse(&field.attrs, false)?;
+			if let Some(rename) = attrs.rename {
+				return Err(syn::Error::new_spanned(rename, "`rename` needs a named field"))
+			}
+			if attrs.skip || attrs.flatten {
+				return Err(syn::Error::new_spanned(
+					field,
+					"`skip` and `flatten` need a named field, a newtype always selects what it wraps",
+				))
+			}
+			let ty = &field.ty;
+			quote!(<#ty as #queryable>::query())
+		},
+		Data::Struct(DataStruct { fields: Fields::Unnamed(fields), .. }) =>
+			return Err(syn::Error::new_spanned(
+				fields,
+				"`Query` can only be derived for tuple structs with one field, i.e newtypes",
+			)),
+		Data::Struct(DataStruct { fields: Fields::Unit, .. }) =>
+			return Err(syn::Error::new_spanned(
+				name,
+				"`Query` can't be derived for unit structs, they have nothing to select",
+			)),
+		Data::Union(data) =>
+			return Err(syn::Error::new_spanned(
+				data.union_token,
+				"`Query` can't be derived for unions",
+			)),
 	};
-	// todo: validate against remote struct.
-	let fields = m
-		.fields
-		.iter()
-		.map(|f| {
-			let rename = get_rename(&f.attrs);
-			let (name, ty) =
-				(rename.or_else(|| Some(format!("{}", f.ident.as_ref().unwrap()))).unwrap(), &f.ty);
-			quote! {
-				query.push_str(&format!("{} {} ", #name, <#ty as Queryable>::query()));
+
+	let (impl_gen, type_gen, where_clause) = generics.split_for_impl();
+	Ok(quote! {
+		impl #impl_gen #queryable for #name #type_gen #where_clause {
+			fn query() -> &'static str {
+				#query
 			}
-		})
-		.collect::<Vec<_>>();
+		}
+	})
+}
 
-	let expanded = quote! {
-		impl #impl_gen Queryable for #name #type_gen #where_clause {
-			fn query() -> String {
-				let mut query = String::new();
-				#(#fields)*
-				format!("{{ {}}}", query)
+/// The `#[query(..)]` attributes of a struct.
+///
+/// serde decides what the fields are deserialized by, so `rename_all` needs the same
+/// `#[serde(rename_all = "..")]`.
+#[derive(Default)]
+struct ContainerAttrs {
+	rename_all: Option<LitStr>,
+}
+
+impl ContainerAttrs {
+	fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+		let mut container = Self::default();
+		let mut seen = vec![];
+		for meta in query_metas(attrs)? {
+			check_duplicate(&mut seen, &meta)?;
+			match &meta {
+				NestedMeta::Meta(Meta::NameValue(name)) if name.path.is_ident("rename_all") => {
+					let rule = expect_str(&name.lit)?;
+					if !RENAME_RULES.contains(&rule.value().as_str()) {
+						return Err(syn::Error::new_spanned(
+							&name.lit,
+							format!("unknown rule, expected one of {}", RENAME_RULES.join(", ")),
+						))
+					}
+					container.rename_all = Some(rule.clone());
+				},
+				meta => return Err(unknown_attr(meta, "rename_all")),
+			}
+		}
+		Ok(container)
+	}
+
+	/// The `rename_all` rule serde applies to the fields of the struct, `attrs` are the struct's.
+	fn rename_all(&self, attrs: &[Attribute]) -> syn::Result<Option<String>> {
+		let serde = serde_attr(attrs, "rename_all");
+		match &self.rename_all {
+			Some(rule) if serde.as_deref() != Some(rule.value().as_str()) =>
+				Err(syn::Error::new_spanned(
+					rule,
+					format!(
+						"`rename_all` needs `#[serde(rename_all = \"{}\")]`, serde decides the names \
+						 of the fields in the response",
+						rule.value()
+					),
+				)),
+			_ => Ok(serde),
+		}
+	}
+}
+
+/// The `#[query(..)]` attributes of a field.
+///
+/// `skip` leaves the field out of the selection, `flatten` splices the selection of its type into
+/// the parent's and `default` keeps it in the selection but tolerates its absence. Only serde
+/// decides how the field is deserialized, so each needs its serde counterpart: `skip` needs
+/// `#[serde(skip)]`, `#[serde(skip_deserializing)]` or `#[serde(default)]`, `default` needs
+/// `#[serde(default)]` and `flatten` needs `#[serde(flatten)]`. A `#[serde(default)]` on the
+/// struct covers all its fields. serde's `skip`, `skip_deserializing` and `flatten` imply the
+/// `query` ones.
+#[derive(Default)]
+struct FieldAttrs {
+	rename: Option<LitStr>,
+	skip: bool,
+	flatten: bool,
+}
+
+impl FieldAttrs {
+	/// `serde_default` is whether the struct is `#[serde(default)]`.
+	fn parse(attrs: &[Attribute], serde_default: bool) -> syn::Result<Self> {
+		let mut field = Self::default();
+		let mut flags = Vec::<Path>::new();
+		let mut seen = vec![];
+		for meta in query_metas(attrs)? {
+			check_duplicate(&mut seen, &meta)?;
+			match &meta {
+				NestedMeta::Meta(Meta::NameValue(name)) if name.path.is_ident("rename") =>
+					field.rename = Some(expect_str(&name.lit)?.clone()),
+				NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+					field.skip = true;
+					flags.push(path.clone());
+				},
+				NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flatten") => {
+					field.flatten = true;
+					flags.push(path.clone());
+				},
+				NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") =>
+					flags.push(path.clone()),
+				meta =>
+					return Err(unknown_attr(
+						meta,
+						"rename = \"..\"`, `skip`, `flatten` or `default",
+					)),
 			}
 		}
+		let (skip, flatten) = (flag(&flags, "skip"), flag(&flags, "flatten"));
+		if let (Some(_), Some(flatten)) = (skip, flatten) {
+			return Err(syn::Error::new_spanned(flatten, "`skip` and `flatten` can't be combined"))
+		}
+		if let (Some(rename), true) = (&field.rename, field.skip || field.flatten) {
+			return Err(syn::Error::new_spanned(
+				rename,
+				"`rename` can't be combined with `skip` or `flatten`, the field isn't selected by name",
+			))
+		}
+
+		// serde decides what's deserialized, it has to agree with the selection.
+		let serde_skip = serde_flag(attrs, &["skip", "skip_deserializing"]);
+		let serde_default = serde_default || serde_flag(attrs, &["default"]);
+		if let Some(skip) = skip.filter(|_| !serde_skip && !serde_default) {
+			return Err(syn::Error::new_spanned(
+				skip,
+				"`skip` needs `#[serde(skip)]` or `#[serde(default)]`, the field isn't in the response",
+			))
+		}
+		if let Some(default) = flag(&flags, "default").filter(|_| !serde_default) {
+			return Err(syn::Error::new_spanned(
+				default,
+				"`default` needs `#[serde(default)]` to fill in the field when it's missing",
+			))
+		}
+		if let Some(flatten) = flatten.filter(|_| !serde_flag(attrs, &["flatten"])) {
+			return Err(syn::Error::new_spanned(
+				flatten,
+				"`flatten` needs `#[serde(flatten)]`, the fields are in the parent's response",
+			))
+		}
+
+		field.skip |= !field.flatten && serde_skip;
+		field.flatten |= !field.skip && serde_flag(attrs, &["flatten"]);
+		Ok(field)
+	}
+}
+
+/// Finds the `name` flag in `flags`.
+fn flag<'a>(flags: &'a [Path], name: &str) -> Option<&'a Path> {
+	flags.iter().find(|path| path.is_ident(name))
+}
+
+/// The metas in all the `#[query(..)]` attributes.
+fn query_metas(attrs: &[Attribute]) -> syn::Result<Vec<NestedMeta>> {
+	let mut metas = vec![];
+	for attr in attrs.iter().filter(|a| a.path.is_ident("query")) {
+		match attr.parse_meta()? {
+			Meta::List(list) => metas.extend(list.nested),
+			meta => return Err(syn::Error::new_spanned(meta, "expected `#[query(..)]`")),
+		}
+	}
+	Ok(metas)
+}
+
+/// Fails if the attribute of `meta` was already set, otherwise adds it to `seen`.
+fn check_duplicate(seen: &mut Vec<String>, meta: &NestedMeta) -> syn::Result<()> {
+	if let NestedMeta::Meta(meta) = meta {
+		let name = meta.path().to_token_stream().to_string();
+		if seen.contains(&name) {
+			let message = format!("duplicate `{}` attribute", name);
+			return Err(syn::Error::new_spanned(meta.path(), message))
+		}
+		seen.push(name);
+	}
+	Ok(())
+}
+
+fn unknown_attr(meta: &NestedMeta, expected: &str) -> syn::Error {
+	syn::Error::new_spanned(meta, format!("unknown `query` attribute, expected `{}`", expected))
+}
+
+fn expect_str(lit: &Lit) -> syn::Result<&LitStr> {
+	match lit {
+		Lit::Str(lstr) => Ok(lstr),
+		lit => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+	}
+}
+
+/// The name a field is selected by, the one serde deserializes it by: its `#[serde(rename)]`,
+/// otherwise its name with the struct's `rename_all` applied. A `#[query(rename)]` that doesn't
+/// agree with it is an error, the response wouldn't deserialize.
+fn field_name(field: &Field, attrs: &FieldAttrs, rename_all: Option<&str>) -> syn::Result<String> {
+	let name = serde_attr(&field.attrs, "rename").unwrap_or_else(|| {
+		let name = field.ident.as_ref().expect("named fields have an ident").to_string();
+		let name = name.trim_start_matches("r#");
+		match rename_all {
+			Some(rule) => rename_field(name, rule),
+			None => name.to_owned(),
+		}
+	});
+
+	match &attrs.rename {
+		Some(rename) if rename.value() != name => Err(syn::Error::new_spanned(
+			rename,
+			format!(
+				"`rename` needs `#[serde(rename = \"{}\")]`, serde deserializes the field as `{}`",
+				rename.value(),
+				name
+			),
+		)),
+		_ => Ok(name),
+	}
+}
+
+/// Renames a snake_case field the way `#[serde(rename_all = "..")]` does.
+fn rename_field(name: &str, rule: &str) -> String {
+	let pascal_case = || {
+		name.split('_')
+			.map(|word| {
+				let mut chars = word.chars();
+				chars
+					.next()
+					.map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+					.unwrap_or_default()
+			})
+			.collect::<String>()
 	};
 
-	// Hand the output tokens back to the compiler
-	TokenStream::from(expanded)
+	match rule {
+		"lowercase" | "snake_case" => name.to_owned(),
+		"UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+		"PascalCase" => pascal_case(),
+		"camelCase" => {
+			let pascal = pascal_case();
+			let mut chars = pascal.chars();
+			chars
+				.next()
+				.map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+				.unwrap_or_default()
+		},
+		"kebab-case" => name.replace('_', "-"),
+		"SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+		_ => name.to_owned(),
+	}
 }
 
-fn get_rename(attrs: &Vec<Attribute>) -> Option<String> {
+/// Whether any of `flags` is set in the `serde` attributes, `default = ".."` counts as `default`.
+fn serde_flag(attrs: &[Attribute], flags: &[&str]) -> bool {
+	serde_metas(attrs).any(|nested| match nested {
+		NestedMeta::Meta(Meta::Path(path)) => flags.iter().any(|flag| path.is_ident(flag)),
+		NestedMeta::Meta(Meta::NameValue(name)) =>
+			flags.iter().any(|flag| name.path.is_ident(flag)),
+		_ => false,
+	})
+}
+
+/// The metas in all the `serde` attributes. These are serde's to validate, anything that doesn't
+/// parse is skipped.
+fn serde_metas(attrs: &[Attribute]) -> impl Iterator<Item = NestedMeta> + '_ {
 	attrs
 		.iter()
-		// this looks like a christmas tree
-		.filter_map(|a| {
-			if a.path.is_ident("query") {
-				if let Meta::List(MetaList { nested, .. }) = a.parse_meta().ok()? {
-					if let Some(NestedMeta::Meta(Meta::NameValue(name))) = nested.first() {
-						if name.path.is_ident("rename") {
-							if let Lit::Str(lstr) = &name.lit {
-								return Some(lstr.value())
-							}
-						}
-					}
-				}
+		.filter(|a| a.path.is_ident("serde"))
+		.filter_map(|a| match a.parse_meta().ok()? {
+			Meta::List(list) => Some(list.nested),
+			_ => None,
+		})
+		.flatten()
+}
+
+/// Finds `key = "value"`, or `key(deserialize = "value")`, in the `serde` attributes.
+fn serde_attr(attrs: &[Attribute], key: &str) -> Option<String> {
+	serde_metas(attrs)
+		.filter_map(|nested| match nested {
+			NestedMeta::Meta(Meta::NameValue(name)) if name.path.is_ident(key) =>
+				expect_str(&name.lit).ok().map(LitStr::value),
+			NestedMeta::Meta(Meta::List(list)) if list.path.is_ident(key) =>
+				list.nested.iter().find_map(|nested| match nested {
+					NestedMeta::Meta(Meta::NameValue(name))
+						if name.path.is_ident("deserialize") =>
+						expect_str(&name.lit).ok().map(LitStr::value),
+					_ => None,
+				}),
+			_ => None,
+		})
+		.last()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// The names the fields of `input` are selected by.
+	fn field_names(input: DeriveInput) -> syn::Result<Vec<String>> {
+		let rename_all = ContainerAttrs::parse(&input.attrs)?.rename_all(&input.attrs)?;
+		match input.data {
+			Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) => fields
+				.named
+				.iter()
+				.map(|field| {
+					let attrs = FieldAttrs::parse(&field.attrs, false)?;
+					field_name(field, &attrs, rename_all.as_deref())
+				})
+				.collect(),
+			_ => unreachable!("only structs with named fields are renamed"),
+		}
+	}
+
+	#[test]
+	fn renames_like_serde() {
+		let names = |rule: &str| {
+			["user_id", "http_url", "ipv4_addr", "name"]
+				.iter()
+				.map(|name| rename_field(name, rule))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(names("lowercase"), ["user_id", "http_url", "ipv4_addr", "name"]);
+		assert_eq!(names("UPPERCASE"), ["USER_ID", "HTTP_URL", "IPV4_ADDR", "NAME"]);
+		assert_eq!(names("PascalCase"), ["UserId", "HttpUrl", "Ipv4Addr", "Name"]);
+		assert_eq!(names("camelCase"), ["userId", "httpUrl", "ipv4Addr", "name"]);
+		assert_eq!(names("snake_case"), ["user_id", "http_url", "ipv4_addr", "name"]);
+		assert_eq!(names("SCREAMING_SNAKE_CASE"), ["USER_ID", "HTTP_URL", "IPV4_ADDR", "NAME"]);
+		assert_eq!(names("kebab-case"), ["user-id", "http-url", "ipv4-addr", "name"]);
+		assert_eq!(names("SCREAMING-KEBAB-CASE"), ["USER-ID", "HTTP-URL", "IPV4-ADDR", "NAME"]);
+	}
+
+	#[test]
+	fn leaves_acronyms_alone() {
+		// serde doesn't split words on case, fields that aren't snake_case keep their case.
+		assert_eq!(rename_field("HTTPUrl", "snake_case"), "HTTPUrl");
+		assert_eq!(rename_field("HTTPUrl", "kebab-case"), "HTTPUrl");
+		assert_eq!(rename_field("HTTP_url", "camelCase"), "hTTPUrl");
+	}
+
+	#[test]
+	fn rename_wins_over_rename_all() {
+		let names = field_names(parse_quote! {
+			#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+			struct User {
+				user_id: i64,
+				#[serde(rename = "mail")]
+				email_address: String,
+				#[serde(rename(serialize = "ser", deserialize = "de"))]
+				display_name: String,
+				#[query(rename = "kind")]
+				#[serde(rename = "kind")]
+				r#type: String,
+			}
+		});
+		assert_eq!(names.unwrap(), ["USER-ID", "mail", "de", "kind"]);
+	}
+
+	#[test]
+	fn query_rename_all_agrees_with_serde() {
+		let names = field_names(parse_quote! {
+			#[query(rename_all = "camelCase")]
+			#[serde(rename_all = "camelCase")]
+			struct User {
+				user_id: i64,
+				#[query(rename = "type")]
+				r#type: String,
+			}
+		});
+		assert_eq!(names.unwrap(), ["userId", "type"]);
+	}
+
+	#[test]
+	fn rejects_renames_serde_disagrees_with() {
+		let error = |input: DeriveInput| field_names(input).unwrap_err().to_string();
+		assert!(error(parse_quote! {
+			struct User {
+				#[query(rename = "query")]
+				#[serde(rename = "serde")]
+				r#type: String,
+			}
+		})
+		.starts_with("`rename` needs `#[serde(rename = \"query\")]`"));
+		// serde deserializes `user_id` without a rename of its own.
+		assert!(error(parse_quote! {
+			struct User {
+				#[query(rename = "userId")]
+				user_id: i64,
+			}
+		})
+		.ends_with("serde deserializes the field as `user_id`"));
+		assert!(error(parse_quote! {
+			#[query(rename_all = "camelCase")]
+			#[serde(rename_all = "PascalCase")]
+			struct User {
+				user_id: i64,
+			}
+		})
+		.starts_with("`rename_all` needs `#[serde(rename_all = \"camelCase\")]`"));
+		assert!(error(parse_quote! {
+			#[query(rename_all = "camelCase")]
+			struct User {
+				user_id: i64,
 			}
-			None
 		})
-		.collect::<Vec<_>>()
-		.pop()
+		.starts_with("`rename_all` needs"));
+	}
 }
@@ -0,0 +1,59 @@
+use prisma_client::{Query, Queryable};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Query, Deserialize, Debug, Default, PartialEq)]
+struct Timestamps {
+	created_at: i64,
+	updated_at: i64,
+}
+
+#[derive(Query, Deserialize, Debug, PartialEq)]
+struct Post {
+	id: i64,
+	#[query(skip)]
+	#[serde(default)]
+	word_count: usize,
+	#[serde(skip)]
+	cached: Option<String>,
+	#[query(flatten)]
+	#[serde(flatten)]
+	timestamps: Timestamps,
+	#[query(default)]
+	#[serde(default)]
+	tags: Vec<String>,
+}
+
+#[test]
+fn skips_flattens_and_defaults() {
+	assert_eq!(Post::query(), "{ id  created_at  updated_at tags  }");
+
+	let response = json!({ "id": 1, "created_at": 2, "updated_at": 3, "tags": ["rust"] });
+	let post: Post = serde_json::from_value(response).unwrap();
+	let timestamps = Timestamps { created_at: 2, updated_at: 3 };
+	let tags = vec!["rust".to_owned()];
+	assert_eq!(post, Post { id: 1, word_count: 0, cached: None, timestamps, tags });
+
+	// `tags` is selected but tolerated missing.
+	let response = json!({ "id": 1, "created_at": 2, "updated_at": 3 });
+	let post: Post = serde_json::from_value(response).unwrap();
+	assert!(post.tags.is_empty());
+}
+
+#[derive(Query, Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+struct Defaults {
+	id: i64,
+	#[query(skip)]
+	computed: String,
+	#[query(default)]
+	name: Option<String>,
+}
+
+#[test]
+fn struct_defaults_cover_fields() {
+	assert_eq!(Defaults::query(), "{ id  name  }");
+
+	let defaults: Defaults = serde_json::from_value(json!({ "id": 1 })).unwrap();
+	assert_eq!(defaults, Defaults { id: 1, ..Default::default() });
+}
@@ -0,0 +1,55 @@
+use prisma_client::{Query, Queryable};
+
+#[derive(Query)]
+struct User {
+	id: i64,
+}
+
+#[derive(Query)]
+struct Post {
+	title: String,
+}
+
+#[derive(Query)]
+struct Page<T: Queryable> {
+	total: i64,
+	items: Vec<T>,
+}
+
+/// Selects a field named after `N`, so every `N` has a selection of its own.
+struct Nth<const N: usize>;
+
+impl<const N: usize> Queryable for Nth<N> {
+	fn query() -> &'static str {
+		["{ zero }", "{ one }", "{ two }"][N]
+	}
+}
+
+#[derive(Query)]
+struct Wrapper<const N: usize> {
+	inner: Nth<N>,
+}
+
+#[derive(Query)]
+struct Borrowed<'a> {
+	name: &'a str,
+}
+
+#[test]
+fn caches_type_parameters_per_instance() {
+	assert_eq!(Page::<User>::query(), "{ total  items { id  } }");
+	assert_eq!(Page::<Post>::query(), "{ total  items { title  } }");
+	assert_eq!(Page::<User>::query(), "{ total  items { id  } }");
+}
+
+#[test]
+fn caches_const_parameters_per_instance() {
+	assert_eq!(Wrapper::<1>::query(), "{ inner { one } }");
+	assert_eq!(Wrapper::<2>::query(), "{ inner { two } }");
+	assert_eq!(Wrapper::<1>::query(), "{ inner { one } }");
+}
+
+#[test]
+fn lifetimes_share_a_selection() {
+	assert_eq!(Borrowed::query(), "{ name  }");
+}
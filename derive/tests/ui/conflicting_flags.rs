@@ -0,0 +1,22 @@
+use prisma_client::Query;
+
+#[derive(Query)]
+struct User {
+	id: i64,
+	#[query(skip, flatten)]
+	profile: Profile,
+}
+
+#[derive(Query)]
+struct Post {
+	id: i64,
+	#[query(flatten, rename = "author")]
+	user: User,
+}
+
+#[derive(Query)]
+struct Profile {
+	bio: String,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use prisma_client::Query;
+
+#[derive(Query)]
+struct User {
+	id: i64,
+	#[query(skip, skip)]
+	computed: String,
+}
+
+#[derive(Query)]
+#[query(rename_all = "camelCase")]
+#[query(rename_all = "snake_case")]
+struct Post {
+	id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use prisma_client::Query;
+use serde::Deserialize;
+
+#[derive(Query, Deserialize)]
+struct Post {
+	id: i64,
+	#[query(skip)]
+	word_count: usize,
+}
+
+#[derive(Query, Deserialize)]
+struct Tags {
+	#[query(default)]
+	tags: Vec<String>,
+}
+
+#[derive(Query, Deserialize)]
+struct User {
+	id: i64,
+	#[query(flatten)]
+	timestamps: Timestamps,
+}
+
+#[derive(Query, Deserialize)]
+struct Timestamps {
+	created_at: i64,
+}
+
+fn main() {}
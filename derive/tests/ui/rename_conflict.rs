@@ -0,0 +1,19 @@
+use prisma_client::Query;
+use serde::Deserialize;
+
+#[derive(Query, Deserialize)]
+struct User {
+	id: i64,
+	#[query(rename = "mail")]
+	#[serde(rename = "email")]
+	email_address: String,
+}
+
+#[derive(Query, Deserialize)]
+#[query(rename_all = "camelCase")]
+#[serde(rename_all = "PascalCase")]
+struct Post {
+	view_count: i64,
+}
+
+fn main() {}
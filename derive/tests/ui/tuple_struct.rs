@@ -0,0 +1,6 @@
+use prisma_client::Query;
+
+#[derive(Query)]
+struct Point(i64, i64);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use prisma_client::Query;
+
+#[derive(Query)]
+union Number {
+	int: i64,
+	float: f64,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use prisma_client::Query;
+
+#[derive(Query)]
+#[query(rename_all = "camelCase", deny_unknown_fields)]
+struct User {
+	id: i64,
+}
+
+#[derive(Query)]
+struct Post {
+	#[query(alias = "postId")]
+	id: i64,
+}
+
+#[derive(Query)]
+#[query(rename_all = "Title Case")]
+struct Comment {
+	id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,257 @@
+//! Graphql inline-argument parsing.
+//!
+//! the inverse of [`to_query_args`](crate::serialization::to_query_args), for reading logged
+//! queries back into structured data and testing the serializer.
+//!
+//! ```rust
+//! use prisma_client::deserialization::from_query_args;
+//!
+//! let user: serde_json::Value = from_query_args(r#"{ name: "Seun", role: ADMIN }"#).unwrap();
+//! ```
+//! This produces `{ "name": "Seun", "role": "ADMIN" }`, graphql enum values are read as strings.
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::{Map, Number, Value};
+
+/// Parses `args` into `T`.
+pub fn from_query_args<T>(args: &str) -> Result<T>
+where
+	T: DeserializeOwned,
+{
+	let mut parser = Parser { input: args, position: 0 };
+	let value = parser.parse_value()?;
+	parser.skip_ignored();
+	if parser.position != args.len() {
+		return Err(parser.error("the end of the arguments"))
+	}
+
+	Ok(serde_json::from_value(value)?)
+}
+
+/// Deserializes `null` as `Some(None)`, for the `Option<Option<T>>` fields of the generated
+/// inputs, where `null` is a value of its own, e.g `{ name: null }` matches the records without
+/// a name. Missing fields are `None` with `#[serde(default)]`.
+pub fn nullable<'de, T, D>(deserializer: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+	T: Deserialize<'de>,
+	D: Deserializer<'de>,
+{
+	Option::deserialize(deserializer).map(Some)
+}
+
+/// Parsing Errors
+#[derive(derive_more::From, derive_more::Display, Debug)]
+pub enum Error {
+	/// The arguments aren't valid graphql.
+	#[from(ignore)]
+	#[display(fmt = "expected {} at position {}", expected, position)]
+	Syntax { expected: &'static str, position: usize },
+	/// The arguments don't match the type they're parsed into.
+	Json(serde_json::Error),
+}
+
+impl std::error::Error for Error {}
+
+type Result<T> = std::result::Result<T, Error>;
+
+struct Parser<'a> {
+	input: &'a str,
+	position: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn parse_value(&mut self) -> Result<Value> {
+		self.skip_ignored();
+		match self.peek() {
+			Some(b'{') => self.parse_object(),
+			Some(b'[') => self.parse_list(),
+			Some(b'"') => self.parse_string().map(Value::String),
+			Some(b'-') | Some(b'0'..=b'9') => self.parse_number(),
+			Some(byte) if is_name_start(byte) => Ok(match self.parse_name() {
+				"true" => Value::Bool(true),
+				"false" => Value::Bool(false),
+				"null" => Value::Null,
+				// an enum value.
+				name => Value::String(name.to_owned()),
+			}),
+			_ => Err(self.error("a value")),
+		}
+	}
+
+	fn parse_object(&mut self) -> Result<Value> {
+		self.expect(b'{')?;
+		let mut object = Map::new();
+		loop {
+			self.skip_ignored();
+			let key = match self.peek() {
+				Some(b'}') => break,
+				// non-string map keys are written as strings.
+				Some(b'"') => self.parse_string()?,
+				Some(byte) if is_name_start(byte) => self.parse_name().to_owned(),
+				_ => return Err(self.error("a key")),
+			};
+			self.skip_ignored();
+			self.expect(b':')?;
+			let value = self.parse_value()?;
+			object.insert(key, value);
+		}
+		self.expect(b'}')?;
+		Ok(Value::Object(object))
+	}
+
+	fn parse_list(&mut self) -> Result<Value> {
+		self.expect(b'[')?;
+		let mut list = vec![];
+		loop {
+			self.skip_ignored();
+			if self.peek() == Some(b']') {
+				break
+			}
+			list.push(self.parse_value()?);
+		}
+		self.expect(b']')?;
+		Ok(Value::Array(list))
+	}
+
+	/// Strings are escaped the way json escapes them.
+	fn parse_string(&mut self) -> Result<String> {
+		let start = self.position;
+		self.expect(b'"')?;
+		loop {
+			match self.peek() {
+				Some(b'"') => break,
+				Some(b'\\') => self.position += 2,
+				Some(_) => self.position += 1,
+				None => return Err(self.error("the end of the string")),
+			}
+		}
+		self.position += 1;
+		Ok(serde_json::from_str(&self.input[start..self.position])?)
+	}
+
+	fn parse_number(&mut self) -> Result<Value> {
+		let start = self.position;
+		while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+			self.position += 1;
+		}
+		match self.input[start..self.position].parse::<Number>() {
+			Ok(number) => Ok(Value::Number(number)),
+			Err(_) => Err(Error::Syntax { expected: "a number", position: start }),
+		}
+	}
+
+	fn parse_name(&mut self) -> &'a str {
+		let start = self.position;
+		while self.peek().map_or(false, |byte| is_name_start(byte) || byte.is_ascii_digit()) {
+			self.position += 1;
+		}
+		&self.input[start..self.position]
+	}
+
+	/// Skips whitespace and commas, graphql ignores both.
+	fn skip_ignored(&mut self) {
+		while let Some(b' ' | b'\t' | b'\n' | b'\r' | b',') = self.peek() {
+			self.position += 1;
+		}
+	}
+
+	fn expect(&mut self, byte: u8) -> Result<()> {
+		if self.peek() != Some(byte) {
+			return Err(self.error(match byte {
+				b'{' => "`{`",
+				b'}' => "`}`",
+				b'[' => "`[`",
+				b']' => "`]`",
+				b':' => "`:`",
+				_ => "`\"`",
+			}))
+		}
+		self.position += 1;
+		Ok(())
+	}
+
+	fn peek(&self) -> Option<u8> {
+		self.input.as_bytes().get(self.position).copied()
+	}
+
+	fn error(&self, expected: &'static str) -> Error {
+		Error::Syntax { expected, position: self.position }
+	}
+}
+
+fn is_name_start(byte: u8) -> bool {
+	byte == b'_' || byte.is_ascii_alphabetic()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{serialization::to_query_args, BigInt, Bytes, Json};
+	use serde::{Deserialize, Serialize};
+	use std::collections::BTreeMap;
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq)]
+	enum Role {
+		Admin,
+		User,
+	}
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq)]
+	struct Person {
+		name: String,
+		age: u64,
+		height: f64,
+		role: Role,
+		nickname: Option<String>,
+		friends: Vec<Person>,
+		scores: BTreeMap<u32, i64>,
+		avatar: Bytes,
+		views: BigInt,
+		meta: Json<BTreeMap<String, Vec<String>>>,
+	}
+
+	fn person(name: &str) -> Person {
+		Person {
+			name: name.into(),
+			age: 22,
+			height: -1.5e-3,
+			role: Role::Admin,
+			nickname: None,
+			friends: vec![],
+			scores: vec![(1, -4), (20, i64::MAX)].into_iter().collect(),
+			avatar: Bytes(vec![0, 159, 255]),
+			views: BigInt(i64::MIN),
+			meta: Json(vec![("tags".into(), vec!["a\"}".into()])].into_iter().collect()),
+		}
+	}
+
+	#[test]
+	fn round_trips() {
+		let mut seun = person("Seun \"Lanlege\"\n\t\\ 🦀 \u{1}");
+		seun.nickname = Some("{ not: an, object }".into());
+		seun.friends = vec![person("Alice"), person("Bob")];
+		seun.friends[1].role = Role::User;
+
+		let args = to_query_args(&seun).unwrap();
+		assert_eq!(seun, from_query_args::<Person>(&args).unwrap());
+	}
+
+	#[test]
+	fn parses_graphql() {
+		let args = r#"{ where: { id: { in: [1, 2,3] } }, orderBy: [{ name: desc }] }"#;
+		let expected = serde_json::json!({
+			"where": { "id": { "in": [1, 2, 3] } },
+			"orderBy": [{ "name": "desc" }],
+		});
+		assert_eq!(expected, from_query_args::<Value>(args).unwrap());
+	}
+
+	#[test]
+	fn reports_syntax_errors() {
+		let error = from_query_args::<Value>(r#"{ name: "Seun" "#).unwrap_err();
+		assert!(matches!(error, Error::Syntax { expected: "a key", position: 15 }));
+
+		let error = from_query_args::<Value>(r#"{ name: "Seun" } }"#).unwrap_err();
+		let expected = "the end of the arguments";
+		assert!(matches!(error, Error::Syntax { expected: e, position: 17 } if e == expected));
+	}
+}
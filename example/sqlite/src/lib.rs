@@ -1,10 +1,13 @@
 #[cfg(test)]
 mod tests {
 	use prisma_client::{
-		FindManyUserArgs, IntFilter, Prisma, Query, UserCreateInput, UserWhereInput,
-		UserWhereInputId,
+		deserialization::from_query_args, post, serialization::to_query_args, user,
+		FindManyPostArgs, FindManyUserArgs, IntWithAggregatesFilter, NestedFloatFilter,
+		PostCreateInput, PostOrderBy, PostScalarWhereWithAggregatesInput, Prisma, Query, SortOrder,
+		UserCreateInput, UserCreateManyInput, UserCreateNestedOneWithoutPostsInput, UserWhereInput,
+		UserWhereUniqueInput,
 	};
-	use serde::Deserialize;
+	use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 	#[derive(Query, Deserialize, Debug)]
 	struct User {
@@ -13,21 +16,17 @@ mod tests {
 		name: String,
 	}
 
+	// `derive(Query)` picks up serde's renames, so the selection matches what's deserialized.
 	#[derive(Clone, Deserialize, Debug, Query)]
+	#[serde(rename_all = "camelCase")]
 	pub struct Post {
 		pub id: i64,
 		pub title: String,
 		pub published: bool,
 		#[serde(skip_serializing_if = "Option::is_none")]
 		pub content: Option<String>,
-		#[query(rename = "viewCount")]
-		#[serde(rename = "viewCount")]
 		pub view_count: i64,
-		#[query(rename = "createdAt")]
-		#[serde(rename = "createdAt")]
 		pub created_at: chrono::DateTime<chrono::Utc>,
-		#[query(rename = "updatedAt")]
-		#[serde(rename = "updatedAt")]
 		pub updated_at: chrono::DateTime<chrono::Utc>,
 	}
 
@@ -37,31 +36,90 @@ mod tests {
 		posts: Vec<Post>,
 	}
 
+	#[derive(Deserialize, Debug)]
+	struct Counts {
+		count_posts: i64,
+		posts_exist: bool,
+	}
+
+	/// Serializing what's parsed back must give the same arguments.
+	fn round_trip<T: Serialize + DeserializeOwned>(value: T) {
+		let args = to_query_args(&value).unwrap();
+		let parsed: T = from_query_args(&args).unwrap();
+		assert_eq!(args, to_query_args(&parsed).unwrap());
+	}
+
+	#[test]
+	fn query_args_round_trip() {
+		round_trip(
+			UserCreateInput::builder()
+				.name("Seun \"Lanlege\" \\ 🦀")
+				.email("seun@squasdrelabs.i\n")
+				.build(),
+		);
+		round_trip(
+			user::id::in_([1, 3, 5, 7]) & !user::name::equals("Seun Lanlege") |
+				user::posts::some(post::title::contains("{ a: [1] }")),
+		);
+		// `{ name: null }`, users without a name.
+		round_trip(UserWhereInput { name: Some(None), ..Default::default() });
+	}
+
 	#[tokio::test]
 	async fn basic_crud() {
 		let client = Prisma::new(vec![]).await.unwrap();
 
 		let user = client
-			.create_user::<User>(UserCreateInput {
-				name: Some("Seun Lanlege".into()),
-				email: "seun@squasdrelabs.i".into(),
-				..Default::default()
-			})
+			.create_user::<User>(
+				UserCreateInput::builder()
+					.name("Seun Lanlege")
+					.email("seun@squasdrelabs.i")
+					.build(),
+			)
 			.await
 			.unwrap();
 
 		println!("{:#?}", user);
 
+		let found = client
+			.user::<User>(UserWhereUniqueInput::by_email(user.email.clone()))
+			.await
+			.unwrap();
+		assert_eq!(found.map(|found| found.id), Some(user.id));
+
+		// sqlite doesn't support `createMany`, so this is emulated with `createOne`.
+		let email = |name: &str| format!("{}@example.com", unique_title(name).replace(' ', "-"));
+		let (alice, bob, carol) = (email("alice"), email("bob"), email("carol"));
+		let created = client
+			.create_many_users(
+				vec![
+					UserCreateManyInput::builder().email(alice.clone()).build(),
+					UserCreateManyInput::builder().email(bob.clone()).build(),
+					UserCreateManyInput::builder().email(alice.clone()).build(),
+				],
+				true,
+			)
+			.await
+			.unwrap();
+		assert_eq!(created, 2);
+
+		// without `skip_duplicates` the duplicate fails the whole transaction, carol included.
+		let failed = client
+			.create_many_users(
+				vec![
+					UserCreateManyInput::builder().email(carol.clone()).build(),
+					UserCreateManyInput::builder().email(bob).build(),
+				],
+				false,
+			)
+			.await;
+		assert!(failed.is_err());
+		assert_eq!(client.count_users(user::email::equals(carol)).await.unwrap(), 0);
+
 		let response = client
 			.transaction()
 			.users::<User>(FindManyUserArgs {
-				filter: Some(UserWhereInput {
-					id: Some(UserWhereInputId::IntFilter(IntFilter {
-						within: Some(vec![1, 3, 5, 7]),
-						..Default::default()
-					})),
-					..Default::default()
-				}),
+				filter: Some(user::id::in_([1, 3, 5, 7]) & !user::name::equals("Seun Lanlege")),
 				..Default::default()
 			})
 			.unwrap()
@@ -74,4 +132,186 @@ mod tests {
 
 		// println!("{:#?}", users);
 	}
+
+	/// A title no earlier run used, the example database keeps its records between runs.
+	fn unique_title(test: &str) -> String {
+		format!("{} {}", test, chrono::Utc::now().timestamp_nanos())
+	}
+
+	#[tokio::test]
+	async fn aggregates() {
+		let client = Prisma::new(vec![]).await.unwrap();
+		let title = unique_title("aggregates");
+		for (view_count, published) in vec![(10, true), (20, true), (60, false)] {
+			let post = PostCreateInput::builder()
+				.title(title.clone())
+				.view_count(view_count)
+				.published(published)
+				.build();
+			client.create_post::<Post>(post).await.unwrap();
+		}
+
+		let aggregate = client
+			.aggregate_posts()
+			.filter(post::title::equals(title.clone()))
+			.count()
+			.avg(|f| f.view_count)
+			.execute()
+			.await
+			.unwrap();
+		assert_eq!(aggregate.count.and_then(|count| count.all), Some(3));
+		assert_eq!(aggregate.avg.and_then(|avg| avg.view_count), Some(30.0));
+
+		// only the unpublished group averages more than 20 views.
+		let having = PostScalarWhereWithAggregatesInput {
+			view_count: Some(
+				IntWithAggregatesFilter {
+					avg: Some(NestedFloatFilter { gt: Some(20.0), ..Default::default() }),
+					..Default::default()
+				}
+				.into(),
+			),
+			..Default::default()
+		};
+		let groups = client
+			.group_posts()
+			.by(|f| f.published)
+			.filter(post::title::equals(title.clone()))
+			.having(having)
+			.count()
+			.sum(|f| f.view_count)
+			.execute()
+			.await
+			.unwrap();
+		assert_eq!(groups.len(), 1);
+		assert_eq!(groups[0].published, Some(false));
+		assert_eq!(groups[0].count.as_ref().and_then(|count| count.all), Some(1));
+		assert_eq!(groups[0].sum.as_ref().and_then(|sum| sum.view_count), Some(60));
+	}
+
+	#[tokio::test]
+	async fn counts() {
+		let client = Prisma::new(vec![]).await.unwrap();
+		let title = unique_title("counts");
+		let email = format!("{}@example.com", title.replace(' ', "-"));
+		let author = UserCreateInput::builder().email(email).name("Counts").build();
+		let author = client.create_user::<User>(author).await.unwrap();
+		for published in vec![true, false, false] {
+			let post = PostCreateInput::builder()
+				.title(title.clone())
+				.published(published)
+				.author(UserCreateNestedOneWithoutPostsInput::connect(UserWhereUniqueInput::by_id(
+					author.id.into(),
+				)))
+				.build();
+			client.create_post::<Post>(post).await.unwrap();
+		}
+
+		let ours = || post::title::equals(title.clone());
+		assert_eq!(client.count_posts(ours()).await.unwrap(), 3);
+		assert!(client.posts_exist(ours()).await.unwrap());
+		let missing = post::title::equals(unique_title("counts"));
+		assert!(!client.posts_exist(missing).await.unwrap());
+
+		let posts = client.user_posts_count(UserWhereUniqueInput::by_id(author.id.into()));
+		assert_eq!(posts.await.unwrap(), Some(3));
+		let nobody = client.user_posts_count(UserWhereUniqueInput::by_id(-1));
+		assert_eq!(nobody.await.unwrap(), None);
+
+		let counts = client
+			.transaction()
+			.count_posts(ours() & post::published::equals(false))
+			.unwrap()
+			.posts_exist(ours())
+			.unwrap()
+			.execute::<Counts>()
+			.await
+			.unwrap();
+		assert_eq!(counts.count_posts, 2);
+		assert!(counts.posts_exist);
+	}
+
+	#[tokio::test]
+	async fn filters() {
+		let client = Prisma::new(vec![]).await.unwrap();
+		let title = unique_title("filters");
+		for (view_count, published) in vec![(10, true), (20, false), (30, true)] {
+			// a null `content` would make `content = "x"` null, and the negation below with it.
+			let post = PostCreateInput::builder()
+				.title(title.clone())
+				.content(format!("{} views", view_count))
+				.view_count(view_count)
+				.published(published)
+				.build();
+			client.create_post::<Post>(post).await.unwrap();
+		}
+
+		let ours = || post::title::equals(title.clone());
+		let filter = ours() & (post::view_count::gt(25) | !post::published::equals(true));
+		let posts = client
+			.posts::<Post>(FindManyPostArgs { filter: Some(filter), ..Default::default() })
+			.await
+			.unwrap();
+		let mut view_counts = posts.iter().map(|post| post.view_count).collect::<Vec<_>>();
+		view_counts.sort_unstable();
+		assert_eq!(view_counts, vec![20, 30]);
+
+		let filter = ours() & !(post::view_count::in_([10, 20]) | post::content::equals("x"));
+		assert_eq!(client.count_posts(filter).await.unwrap(), 1);
+	}
+
+	#[tokio::test]
+	async fn ordering() {
+		let client = Prisma::new(vec![]).await.unwrap();
+		let title = unique_title("ordering");
+		let mut emails = vec![];
+		// the authors, with the view count and published flag of each of their posts.
+		for (name, posts) in vec![("Ada", vec![(30, true), (20, false)]), ("Bea", vec![(10, true)])]
+		{
+			let email = format!("{}-{}@example.com", name, title.replace(' ', "-"));
+			let author = UserCreateInput::builder().email(email.clone()).name(name).build();
+			let author = client.create_user::<User>(author).await.unwrap();
+			emails.push(email);
+			for (view_count, published) in posts {
+				let post = PostCreateInput::builder()
+					.title(title.clone())
+					.view_count(view_count)
+					.published(published)
+					.author(UserCreateNestedOneWithoutPostsInput::connect(
+						UserWhereUniqueInput::by_id(author.id.into()),
+					))
+					.build();
+				client.create_post::<Post>(post).await.unwrap();
+			}
+		}
+
+		let view_counts = |order_by: PostOrderBy| {
+			let args = FindManyPostArgs {
+				filter: Some(post::title::equals(title.clone())),
+				order_by: order_by.into(),
+				..Default::default()
+			};
+			let posts = client.posts::<Post>(args);
+			async move { posts.await.unwrap().iter().map(|post| post.view_count).collect::<Vec<_>>() }
+		};
+		let order = post::order().view_count(SortOrder::Asc);
+		assert_eq!(view_counts(order).await, vec![10, 20, 30]);
+		let order = post::order().published(SortOrder::Asc).view_count(SortOrder::Desc);
+		assert_eq!(view_counts(order).await, vec![20, 30, 10]);
+		let order =
+			post::author::name(SortOrder::Desc).then(post::order().view_count(SortOrder::Asc));
+		assert_eq!(view_counts(order).await, vec![10, 20, 30]);
+
+		let names = |order: SortOrder| {
+			let args = FindManyUserArgs {
+				filter: Some(user::email::in_(emails.clone())),
+				order_by: user::posts::count(order).into(),
+				..Default::default()
+			};
+			let users = client.users::<User>(args);
+			async move { users.await.unwrap().into_iter().map(|user| user.name).collect::<Vec<_>>() }
+		};
+		assert_eq!(names(SortOrder::Desc).await, vec!["Ada", "Bea"]);
+		assert_eq!(names(SortOrder::Asc).await, vec!["Bea", "Ada"]);
+	}
 }
@@ -0,0 +1,155 @@
+//! Prisma's `Json` scalar.
+//!
+//! the query engine takes and returns `Json` values as strings of json, [`Json`] does the
+//! (de)serialization so a `Json` column can be read straight into any [`Deserialize`] type.
+//!
+//! ```rust
+//! # use prisma_client::{Json, Query};
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Deserialize, Serialize)]
+//! struct Meta {
+//!     tags: Vec<String>,
+//! }
+//!
+//! #[derive(Query, Deserialize)]
+//! struct Post {
+//!     id: i64,
+//!     meta: Json<Meta>,
+//! }
+//! ```
+//!
+//! Types that can't be wrapped can use the functions in this module instead,
+//! `#[serde(with = "prisma_client::json")]`.
+//!
+//! The `Json` fields of the generated models are `Json<serde_json::Value>` unless the schema
+//! overrides their type. Prisma schemas don't take Rust attributes like
+//! `#[prisma(json = Vec<String>)]`, and reject unknown `@` attributes, so the override is a doc
+//! comment on the field instead:
+//!
+//! ```prisma
+//! model Post {
+//!   id   Int   @id
+//!   /// @json(Vec<String>)
+//!   tags Json?
+//! }
+//! ```
+//!
+//! The override only changes the field of the model. The create and update inputs, and the
+//! filters, still take a `Json<serde_json::Value>`, e.g `Json(serde_json::to_value(&tags)?)`.
+use serde::{
+	de::{DeserializeOwned, Error as _},
+	ser::Error as _,
+	Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::ops::{Deref, DerefMut};
+
+/// A `Json` column, decoded into `T`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Json<T = serde_json::Value>(pub T);
+
+impl<T> Json<T> {
+	/// Returns the decoded value.
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<T> Deref for Json<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T> DerefMut for Json<T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl<T> From<T> for Json<T> {
+	fn from(value: T) -> Self {
+		Json(value)
+	}
+}
+
+impl<T: Serialize> Serialize for Json<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Json<T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Json)
+	}
+}
+
+/// Serializes `value` as a string of json.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: Serialize,
+	S: Serializer,
+{
+	let json = serde_json::to_string(value).map_err(S::Error::custom)?;
+	serializer.serialize_str(&json)
+}
+
+/// Deserializes a string of json, or json that has already been parsed.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+	T: DeserializeOwned,
+	D: Deserializer<'de>,
+{
+	match serde_json::Value::deserialize(deserializer)? {
+		serde_json::Value::String(json) => match serde_json::from_str(&json) {
+			Ok(value) => Ok(value),
+			// a plain string that isn't itself a string of json.
+			Err(_) => T::deserialize(serde_json::Value::String(json)).map_err(D::Error::custom),
+		},
+		value => T::deserialize(value).map_err(D::Error::custom),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq)]
+	struct Meta {
+		tags: Vec<String>,
+	}
+
+	#[test]
+	fn serializes_as_a_string_of_json() {
+		let meta = Json(Meta { tags: vec!["rust".into()] });
+		let value = serde_json::to_value(&meta).unwrap();
+		assert_eq!(value, serde_json::json!(r#"{"tags":["rust"]}"#));
+		assert_eq!(serde_json::from_value::<Json<Meta>>(value).unwrap(), meta);
+	}
+
+	#[test]
+	fn deserializes_parsed_json() {
+		let meta: Json<Meta> = serde_json::from_value(serde_json::json!({ "tags": [] })).unwrap();
+		assert_eq!(meta.into_inner(), Meta { tags: vec![] });
+
+		let value: Json = serde_json::from_value(serde_json::json!([1, null])).unwrap();
+		assert_eq!(*value, serde_json::json!([1, null]));
+	}
+
+	#[test]
+	fn falls_back_to_plain_strings() {
+		// `"rust"` isn't a string of json, it's the json string itself.
+		let plain: Json<String> = serde_json::from_value(serde_json::json!("rust")).unwrap();
+		assert_eq!(plain.0, "rust");
+		let quoted: Json<String> = serde_json::from_value(serde_json::json!(r#""rust""#)).unwrap();
+		assert_eq!(quoted.0, "rust");
+		let value: Json = serde_json::from_value(serde_json::json!("{ unparseable")).unwrap();
+		assert_eq!(value.0, serde_json::json!("{ unparseable"));
+
+		let meta = serde_json::from_value::<Json<Meta>>(serde_json::json!("{ unparseable"));
+		assert!(meta.is_err());
+	}
+}
@@ -1,7 +1,24 @@
 ///
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use std::{
+	any::TypeId,
+	borrow::Cow,
+	collections::HashMap,
+	sync::{Arc, RwLock},
+};
 
+pub mod big_int;
+pub mod bytes;
+pub mod deserialization;
+pub mod json;
 pub mod serialization;
+pub mod variables;
+
+pub use big_int::BigInt;
+pub use bytes::Bytes;
+pub use json::Json;
+pub use variables::Variables;
 
 pub use datamodel::{self, common::preview_features::PreviewFeature};
 pub use graphql_parser;
@@ -12,6 +29,7 @@ pub use prisma_models;
 pub use request_handlers;
 pub use serde;
 pub use log;
+pub use once_cell;
 pub use futures;
 pub use query_connector;
 pub use query_core;
@@ -29,8 +47,37 @@ pub use query_core;
 ///
 /// User::query(); // Produces `{ id name }`, which is then interpolated into a graphql query.
 /// ```
+///
+/// The selection of a type never changes, the derived impls build it once and hand out the same
+/// `&'static str` afterwards. The selections of generic types are cached per instance, by their
+/// [`TypeId`], so the derive adds a `'static` bound to their type parameters.
+///
+/// Fields are renamed with `#[query(rename = "..")]`, left out of the selection with
+/// `#[query(skip)]` and have the fields of their type spliced in with `#[query(flatten)]`. serde's
+/// renames, `skip` and `flatten` are picked up too. The derive can't change how serde deserializes
+/// a field, so `#[query(skip)]` needs `#[serde(skip)]` or `#[serde(default)]`, `#[query(flatten)]`
+/// needs `#[serde(flatten)]`, `#[query(default)]` needs `#[serde(default)]` and a
+/// `#[query(rename)]` or `#[query(rename_all)]` needs the serde one that deserializes the same
+/// name, or it's a compile error.
 pub trait Queryable {
-	fn query() -> String;
+	fn query() -> &'static str;
+}
+
+/// The selections of generic types, which can't be cached in a `static` of their impl as it's
+/// shared by every instance of the type.
+static QUERIES: Lazy<RwLock<HashMap<TypeId, &'static str>>> = Lazy::new(Default::default);
+
+/// Returns the selection of `T`, building it with `build` the first time around.
+pub fn cached_query<T: ?Sized + 'static>(build: impl FnOnce() -> String) -> &'static str {
+	let id = TypeId::of::<T>();
+	if let Some(query) = QUERIES.read().expect("QUERIES lock was poisoned").get(&id) {
+		return query
+	}
+
+	// built outside the lock, `build` takes it too for the generic types it selects.
+	let query = build();
+	let mut queries = QUERIES.write().expect("QUERIES lock was poisoned");
+	queries.entry(id).or_insert_with(|| Box::leak(query.into_boxed_str()))
 }
 
 macro_rules! generate_queryable_impl {
@@ -38,29 +85,82 @@ macro_rules! generate_queryable_impl {
 		$($y:ty),+
 	) => {
 		$(impl Queryable for $y {
-			fn query() -> String {
-				String::new()
+			fn query() -> &'static str {
+				""
 			}
 		})+
 	}
 }
 
-generate_queryable_impl!(i64, i32, i16, i8, f64, f32, &str, bool, String, DateTime<Utc>);
+generate_queryable_impl!(
+	i64,
+	i32,
+	i16,
+	i8,
+	u64,
+	u32,
+	u16,
+	u8,
+	f64,
+	f32,
+	&str,
+	Cow<'_, str>,
+	bool,
+	String,
+	serde_json::Value,
+	DateTime<Utc>,
+	DateTime<FixedOffset>,
+	NaiveDateTime,
+	NaiveDate
+);
+
+#[cfg(feature = "uuid")]
+generate_queryable_impl!(uuid::Uuid);
+
+#[cfg(feature = "rust_decimal")]
+generate_queryable_impl!(rust_decimal::Decimal);
+
+#[cfg(feature = "time")]
+generate_queryable_impl!(time::OffsetDateTime);
 
 impl<T: Queryable> Queryable for Vec<T> {
-	fn query() -> String {
+	fn query() -> &'static str {
 		T::query()
 	}
 }
 
 impl<T: Queryable> Queryable for Option<T> {
-	fn query() -> String {
+	fn query() -> &'static str {
 		T::query()
 	}
 }
 
 impl<T: Queryable> Queryable for Box<T> {
-	fn query() -> String {
+	fn query() -> &'static str {
+		T::query()
+	}
+}
+
+impl<T: Queryable> Queryable for Arc<T> {
+	fn query() -> &'static str {
 		T::query()
 	}
 }
+
+impl Queryable for BigInt {
+	fn query() -> &'static str {
+		""
+	}
+}
+
+impl Queryable for Bytes {
+	fn query() -> &'static str {
+		""
+	}
+}
+
+impl<T> Queryable for Json<T> {
+	fn query() -> &'static str {
+		""
+	}
+}
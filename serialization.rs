@@ -18,24 +18,63 @@
 //!
 //! notice the lack of surrounding quotes of Object keys.
 use serde::{
-	ser::{self, Impossible, SerializeSeq},
+	ser::{self, Impossible},
 	serde_if_integer128, Serialize, Serializer,
 };
-use std::{fmt, fmt::Display, io, num::FpCategory};
+use std::{convert::TryFrom, fmt, fmt::Display, io};
 
 pub fn to_query_args<T>(data: T) -> Result<String>
 where
 	T: Serialize,
 {
-	let mut serializer = QueryArgumentSerializer { writer: Vec::new() };
+	let mut args = String::new();
+	to_query_args_into(&mut args, data)?;
+	Ok(args)
+}
+
+/// Serializes `data` into `writer`.
+pub fn to_query_args_writer<W, T>(writer: W, data: T) -> Result<()>
+where
+	W: io::Write,
+	T: Serialize,
+{
+	let mut serializer = QueryArgumentSerializer { writer };
+	data.serialize(&mut serializer)
+}
+
+/// Appends `data` to `args`, so many arguments can share one buffer. `args` is left as it was if
+/// serialization fails.
+pub fn to_query_args_into<T>(args: &mut String, data: T) -> Result<()>
+where
+	T: Serialize,
+{
+	let len = args.len();
+	let result = to_query_args_writer(StringWriter(&mut *args), data);
+	if result.is_err() {
+		args.truncate(len);
+	}
+	result
+}
 
-	data.serialize(&mut serializer)?;
+/// Writes to a `String`. The serializer only ever writes whole `str`s (or ascii), so checking
+/// every write is valid utf-8 is cheap.
+struct StringWriter<'a>(&'a mut String);
 
-	Ok(unsafe { String::from_utf8_unchecked(serializer.writer) })
+impl io::Write for StringWriter<'_> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let str =
+			std::str::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+		self.0.push_str(str);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
 }
 
-struct QueryArgumentSerializer {
-	writer: Vec<u8>,
+struct QueryArgumentSerializer<W> {
+	writer: W,
 }
 
 /// Serialization Errors
@@ -44,6 +83,18 @@ pub enum Error {
 	KeyMustBeAString,
 	IO(io::Error),
 	Custom(String),
+	/// NaN and infinite floats can't be written in a graphql document.
+	#[display(fmt = "{} isn't a finite float", _0)]
+	#[from(ignore)]
+	NonFiniteFloat(f64),
+	/// Prisma's integers are 64 bit, larger ones need to be sent as `BigInt` or `Decimal`.
+	#[display(fmt = "{} is out of range for a 64 bit integer", _0)]
+	#[from(ignore)]
+	IntegerOutOfRange(String),
+	/// Enum variants are written as graphql enum values, which must be graphql names.
+	#[display(fmt = "{} isn't a valid graphql enum value", _0)]
+	#[from(ignore)]
+	InvalidEnumValue(&'static str),
 }
 
 impl std::error::Error for Error {}
@@ -59,17 +110,24 @@ impl ser::Error for Error {
 
 type Result<T> = std::result::Result<T, Error>;
 
-impl<'a> Serializer for &'a mut QueryArgumentSerializer {
+/// Whether `value` is a graphql name, which enum values have to be.
+pub(crate) fn is_name(value: &str) -> bool {
+	let mut chars = value.chars();
+	chars.next().map_or(false, |c| c == '_' || c.is_ascii_alphabetic()) &&
+		chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+impl<'a, W: io::Write> Serializer for &'a mut QueryArgumentSerializer<W> {
 	type Ok = ();
 	type Error = Error;
 
-	type SerializeSeq = Compound<'a>;
-	type SerializeTuple = Compound<'a>;
-	type SerializeTupleStruct = Compound<'a>;
-	type SerializeTupleVariant = Compound<'a>;
-	type SerializeMap = Compound<'a>;
-	type SerializeStruct = Compound<'a>;
-	type SerializeStructVariant = Compound<'a>;
+	type SerializeSeq = Compound<'a, W>;
+	type SerializeTuple = Compound<'a, W>;
+	type SerializeTupleStruct = Compound<'a, W>;
+	type SerializeTupleVariant = Compound<'a, W>;
+	type SerializeMap = Compound<'a, W>;
+	type SerializeStruct = Compound<'a, W>;
+	type SerializeStructVariant = Compound<'a, W>;
 
 	#[inline]
 	fn serialize_bool(self, value: bool) -> Result<()> {
@@ -103,8 +161,10 @@ impl<'a> Serializer for &'a mut QueryArgumentSerializer {
 
 	serde_if_integer128! {
 		fn serialize_i128(self, value: i128) -> Result<()> {
-			write_number_str(&mut self.writer, &value.to_string())?;
-			Ok(())
+			match i64::try_from(value) {
+				Ok(value) => self.serialize_i64(value),
+				Err(_) => Err(Error::IntegerOutOfRange(value.to_string())),
+			}
 		}
 	}
 
@@ -128,40 +188,37 @@ impl<'a> Serializer for &'a mut QueryArgumentSerializer {
 
 	#[inline]
 	fn serialize_u64(self, value: u64) -> Result<()> {
+		if i64::try_from(value).is_err() {
+			return Err(Error::IntegerOutOfRange(value.to_string()))
+		}
 		write_u64(&mut self.writer, value)?;
 		Ok(())
 	}
 
 	serde_if_integer128! {
 		fn serialize_u128(self, value: u128) -> Result<()> {
-			write_number_str(&mut self.writer, &value.to_string())?;
-			Ok(())
+			match i64::try_from(value) {
+				Ok(value) => self.serialize_i64(value),
+				Err(_) => Err(Error::IntegerOutOfRange(value.to_string())),
+			}
 		}
 	}
 
 	#[inline]
 	fn serialize_f32(self, value: f32) -> Result<()> {
-		match value.classify() {
-			FpCategory::Nan | FpCategory::Infinite => {
-				write_null(&mut self.writer)?;
-			},
-			_ => {
-				write_f32(&mut self.writer, value)?;
-			},
+		if !value.is_finite() {
+			return Err(Error::NonFiniteFloat(value.into()))
 		}
+		write_f32(&mut self.writer, value)?;
 		Ok(())
 	}
 
 	#[inline]
 	fn serialize_f64(self, value: f64) -> Result<()> {
-		match value.classify() {
-			FpCategory::Nan | FpCategory::Infinite => {
-				write_null(&mut self.writer)?;
-			},
-			_ => {
-				write_f64(&mut self.writer, value)?;
-			},
+		if !value.is_finite() {
+			return Err(Error::NonFiniteFloat(value))
 		}
+		write_f64(&mut self.writer, value)?;
 		Ok(())
 	}
 
@@ -178,13 +235,11 @@ impl<'a> Serializer for &'a mut QueryArgumentSerializer {
 		Ok(())
 	}
 
+	/// Prisma's `Bytes` are base64 strings.
 	#[inline]
 	fn serialize_bytes(self, value: &[u8]) -> Result<()> {
-		let mut seq = self.serialize_seq(Some(value.len()))?;
-		for byte in value {
-			seq.serialize_element(byte)?;
-		}
-		SerializeSeq::end(seq)
+		format_escaped_str(&mut self.writer, &base64::encode(value))?;
+		Ok(())
 	}
 
 	#[inline]
@@ -212,13 +267,18 @@ impl<'a> Serializer for &'a mut QueryArgumentSerializer {
 	}
 
 	#[inline]
+	/// Enums are written as graphql enum values, i.e without quotes.
 	fn serialize_unit_variant(
 		self,
 		_name: &'static str,
 		_variant_index: u32,
 		variant: &'static str,
 	) -> Result<()> {
-		self.serialize_str(variant)
+		if !is_name(variant) {
+			return Err(Error::InvalidEnumValue(variant))
+		}
+		self.writer.write_all(variant.as_bytes())?;
+		Ok(())
 	}
 
 	/// Serialize newtypes without an object wrapper.
@@ -385,12 +445,12 @@ enum State {
 
 // Not public API. Should be pub(crate).
 #[doc(hidden)]
-struct Compound<'a> {
-	ser: &'a mut QueryArgumentSerializer,
+struct Compound<'a, W> {
+	ser: &'a mut QueryArgumentSerializer<W>,
 	state: State,
 }
 
-impl<'a> ser::SerializeSeq for Compound<'a> {
+impl<'a, W: io::Write> ser::SerializeSeq for Compound<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -416,7 +476,7 @@ impl<'a> ser::SerializeSeq for Compound<'a> {
 	}
 }
 
-impl<'a> ser::SerializeTuple for Compound<'a> {
+impl<'a, W: io::Write> ser::SerializeTuple for Compound<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -434,7 +494,7 @@ impl<'a> ser::SerializeTuple for Compound<'a> {
 	}
 }
 
-impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+impl<'a, W: io::Write> ser::SerializeTupleStruct for Compound<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -452,7 +512,7 @@ impl<'a> ser::SerializeTupleStruct for Compound<'a> {
 	}
 }
 
-impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+impl<'a, W: io::Write> ser::SerializeTupleVariant for Compound<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -476,7 +536,7 @@ impl<'a> ser::SerializeTupleVariant for Compound<'a> {
 	}
 }
 
-impl<'a> ser::SerializeMap for Compound<'a> {
+impl<'a, W: io::Write> ser::SerializeMap for Compound<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -513,7 +573,7 @@ impl<'a> ser::SerializeMap for Compound<'a> {
 	}
 }
 
-impl<'a> ser::SerializeStruct for Compound<'a> {
+impl<'a, W: io::Write> ser::SerializeStruct for Compound<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -531,7 +591,7 @@ impl<'a> ser::SerializeStruct for Compound<'a> {
 	}
 }
 
-impl<'a> ser::SerializeStructVariant for Compound<'a> {
+impl<'a, W: io::Write> ser::SerializeStructVariant for Compound<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -555,11 +615,11 @@ impl<'a> ser::SerializeStructVariant for Compound<'a> {
 	}
 }
 
-struct MapKeySerializer<'a> {
-	ser: &'a mut QueryArgumentSerializer,
+struct MapKeySerializer<'a, W> {
+	ser: &'a mut QueryArgumentSerializer<W>,
 }
 
-impl<'a> ser::Serializer for MapKeySerializer<'a> {
+impl<'a, W: io::Write> ser::Serializer for MapKeySerializer<'a, W> {
 	type Ok = ();
 	type Error = Error;
 
@@ -1211,4 +1271,65 @@ mod tests {
 
 		assert_eq!(r###"{name:"Seun",age:22}"###, to_query_args(person).unwrap())
 	}
+
+	#[test]
+	fn writes_into_buffers() {
+		let person = Person { name: "Seun \"Lanlege\"".into(), age: 22 };
+
+		let mut args = String::from("data: ");
+		to_query_args_into(&mut args, &person).unwrap();
+		assert_eq!(r###"data: {name:"Seun \"Lanlege\"",age:22}"###, args);
+
+		let mut bytes = vec![];
+		to_query_args_writer(&mut bytes, &person).unwrap();
+		assert_eq!(args.as_bytes()[6..], bytes[..]);
+	}
+
+	#[test]
+	fn bytes_are_base64() {
+		let bytes = crate::Bytes(vec![0, 1, 2, 254, 255]);
+		assert_eq!(r#""AAEC/v8=""#, to_query_args(&bytes).unwrap());
+
+		let decoded: crate::Bytes = serde_json::from_str(r#""AAEC/v8=""#).unwrap();
+		assert_eq!(bytes, decoded);
+	}
+
+	#[test]
+	fn enums_are_graphql_enum_values() {
+		#[derive(Serialize)]
+		enum Role {
+			Admin,
+			#[serde(rename = "not-a-name")]
+			Invalid,
+		}
+
+		assert_eq!("[Admin]", to_query_args(&[Role::Admin]).unwrap());
+		assert!(matches!(
+			to_query_args(&Role::Invalid),
+			Err(Error::InvalidEnumValue("not-a-name"))
+		));
+	}
+
+	#[test]
+	fn rejects_unrepresentable_numbers() {
+		assert!(matches!(to_query_args(f64::NAN), Err(Error::NonFiniteFloat(_))));
+		assert!(matches!(to_query_args(f32::INFINITY), Err(Error::NonFiniteFloat(_))));
+		assert!(matches!(to_query_args(u64::MAX), Err(Error::IntegerOutOfRange(_))));
+		assert!(matches!(to_query_args(i128::MIN), Err(Error::IntegerOutOfRange(_))));
+		assert_eq!("42", to_query_args(42u128).unwrap());
+		assert_eq!(
+			r#""9007199254740993""#,
+			to_query_args(crate::BigInt(9007199254740993)).unwrap()
+		);
+	}
+
+	#[test]
+	fn leaves_the_buffer_on_errors() {
+		let mut map = std::collections::BTreeMap::new();
+		map.insert(vec![1], 1);
+
+		let mut args = String::from("data: ");
+		assert!(to_query_args_into(&mut args, &map).is_err());
+		assert_eq!("data: ", args);
+	}
 }
@@ -0,0 +1,611 @@
+//! Binding values into parsed documents.
+//!
+//! A generated method's document only depends on its selection, so it's parsed once and cached in
+//! [`Documents`], with placeholders where its arguments go.
+//!
+//! ```text
+//! query($where: UserWhereInput, $take: Int) { findManyUser(where: $where, take: $take) { id } }
+//! ```
+//!
+//! `GraphQLProtocolAdapter::convert` only takes a document and an operation name, there's no way
+//! to pass values apart from it. So [`Variables::bind`] replaces the placeholders with their
+//! values, as literals, in a copy of the parsed document, which is what the engine gets.
+use crate::serialization::{is_name, Error};
+use graphql_parser::query::{
+	self, parse_query, Definition, OperationDefinition, ParseError, Selection, SelectionSet,
+};
+use once_cell::sync::OnceCell;
+use serde::{ser, serde_if_integer128, Serialize, Serializer};
+use std::{
+	collections::{BTreeMap, HashMap},
+	convert::TryFrom,
+	sync::{Mutex, PoisonError},
+};
+
+/// A parsed document, see [`Documents`].
+pub type Document = query::Document<'static, String>;
+
+/// A graphql value, what a variable is set to.
+pub type Value = query::Value<'static, String>;
+
+/// The values of the `$variables` of a document, serialized by [`to_value`].
+#[derive(Clone, Debug, Default)]
+pub struct Variables {
+	values: Vec<(&'static str, Value)>,
+}
+
+impl Variables {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets `$name` to `value`.
+	pub fn insert<T: Serialize>(&mut self, name: &'static str, value: T) -> Result<(), Error> {
+		let value = to_value(value)?;
+		self.insert_value(name, value);
+		Ok(())
+	}
+
+	/// Sets `$name` to a value that's already been serialized.
+	pub fn insert_value(&mut self, name: &'static str, value: Value) {
+		match self.values.iter_mut().find(|(key, _)| *key == name) {
+			Some((_, old)) => *old = value,
+			None => self.values.push((name, value)),
+		}
+	}
+
+	/// Returns the value of `$name`.
+	pub fn get(&self, name: &str) -> Option<&Value> {
+		self.values.iter().find(|(key, _)| *key == name).map(|(_, value)| value)
+	}
+
+	/// The names of the variables that have a value.
+	pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+		self.values.iter().map(|(name, _)| *name)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+
+	/// Returns a copy of `document` with the variables replaced by their values, and without its
+	/// variable definitions. An argument whose variable has no value is left out, the way graphql
+	/// leaves out a missing optional variable.
+	pub fn bind(&self, document: &Document) -> Document {
+		let mut document = document.clone();
+		for definition in &mut document.definitions {
+			let selection_set = match definition {
+				Definition::Operation(OperationDefinition::SelectionSet(selection_set)) =>
+					selection_set,
+				Definition::Operation(OperationDefinition::Query(query)) => {
+					query.variable_definitions.clear();
+					&mut query.selection_set
+				},
+				Definition::Operation(OperationDefinition::Mutation(mutation)) => {
+					mutation.variable_definitions.clear();
+					&mut mutation.selection_set
+				},
+				Definition::Operation(OperationDefinition::Subscription(subscription)) => {
+					subscription.variable_definitions.clear();
+					&mut subscription.selection_set
+				},
+				Definition::Fragment(fragment) => &mut fragment.selection_set,
+			};
+			self.bind_selection_set(selection_set);
+		}
+		document
+	}
+
+	fn bind_selection_set(&self, selection_set: &mut SelectionSet<'static, String>) {
+		for selection in &mut selection_set.items {
+			match selection {
+				Selection::Field(field) => {
+					let arguments = std::mem::take(&mut field.arguments);
+					field.arguments = arguments
+						.into_iter()
+						.filter_map(|(name, value)| Some((name, self.bind_value(value)?)))
+						.collect();
+					self.bind_selection_set(&mut field.selection_set);
+				},
+				Selection::InlineFragment(fragment) =>
+					self.bind_selection_set(&mut fragment.selection_set),
+				Selection::FragmentSpread(_) => {},
+			}
+		}
+	}
+
+	/// Replaces the variables in `value`, `None` if it's a variable without a value.
+	fn bind_value(&self, value: Value) -> Option<Value> {
+		match value {
+			Value::Variable(name) => self.get(&name).cloned(),
+			Value::List(values) => Some(Value::List(
+				values
+					.into_iter()
+					.map(|value| self.bind_value(value).unwrap_or(Value::Null))
+					.collect(),
+			)),
+			Value::Object(fields) => Some(Value::Object(
+				fields
+					.into_iter()
+					.filter_map(|(name, value)| Some((name, self.bind_value(value)?)))
+					.collect(),
+			)),
+			value => Some(value),
+		}
+	}
+}
+
+/// The parsed documents of a generated method, one per selection, so a method's query is only
+/// parsed the first time it's made.
+///
+/// The documents are never freed, there's one for every selection a method has been called with.
+/// A derived [`Queryable`](crate::Queryable) always has the same selection, so they're bounded by
+/// the types a method is called with, but the aggregate builders select what they're asked for,
+/// one document for each combination of aggregates.
+#[derive(Debug, Default)]
+pub struct Documents {
+	documents: OnceCell<Mutex<HashMap<String, &'static Document>>>,
+}
+
+impl Documents {
+	pub const fn new() -> Self {
+		Self { documents: OnceCell::new() }
+	}
+
+	/// Returns the document for `selection`, which is empty for documents that don't take one.
+	/// `query` is only called, and parsed, the first time.
+	pub fn get_or_parse<F>(
+		&self,
+		selection: &str,
+		query: F,
+	) -> Result<&'static Document, ParseError>
+	where
+		F: FnOnce() -> String,
+	{
+		let documents = self.documents.get_or_init(Default::default);
+		let mut documents = documents.lock().unwrap_or_else(PoisonError::into_inner);
+		if let Some(document) = documents.get(selection) {
+			return Ok(document)
+		}
+
+		// a document can't outlive the text it's parsed from. Both are kept for good, there's one
+		// per method and selection.
+		let query: &'static str = Box::leak(query().into_boxed_str());
+		let document: &'static Document = Box::leak(Box::new(parse_query(query)?));
+		documents.insert(selection.to_owned(), document);
+		Ok(document)
+	}
+}
+
+/// Serializes `data` to a graphql value, following the rules of
+/// [`to_query_args`](crate::serialization::to_query_args).
+pub fn to_value<T: Serialize>(data: T) -> Result<Value, Error> {
+	data.serialize(ValueSerializer)
+}
+
+/// `{ variant: value }`, how enum variants with data are written.
+fn variant(variant: &'static str, value: Value) -> Value {
+	Value::Object(std::iter::once((variant.to_owned(), value)).collect())
+}
+
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+	type Ok = Value;
+	type Error = Error;
+
+	type SerializeSeq = SerializeList;
+	type SerializeTuple = SerializeList;
+	type SerializeTupleStruct = SerializeList;
+	type SerializeTupleVariant = SerializeList;
+	type SerializeMap = SerializeObject;
+	type SerializeStruct = SerializeObject;
+	type SerializeStructVariant = SerializeObject;
+
+	fn serialize_bool(self, value: bool) -> Result<Value, Error> {
+		Ok(Value::Boolean(value))
+	}
+
+	fn serialize_i8(self, value: i8) -> Result<Value, Error> {
+		self.serialize_i64(value.into())
+	}
+
+	fn serialize_i16(self, value: i16) -> Result<Value, Error> {
+		self.serialize_i64(value.into())
+	}
+
+	fn serialize_i32(self, value: i32) -> Result<Value, Error> {
+		self.serialize_i64(value.into())
+	}
+
+	/// `Number` only holds an `i32`, a graphql `Int`. Larger values are prisma `BigInt`s, which
+	/// serialize as strings, see [`BigInt`](crate::BigInt).
+	fn serialize_i64(self, value: i64) -> Result<Value, Error> {
+		match i32::try_from(value) {
+			Ok(value) => Ok(Value::Int(value.into())),
+			Err(_) => Err(Error::IntegerOutOfRange(value.to_string())),
+		}
+	}
+
+	serde_if_integer128! {
+		fn serialize_i128(self, value: i128) -> Result<Value, Error> {
+			match i64::try_from(value) {
+				Ok(value) => self.serialize_i64(value),
+				Err(_) => Err(Error::IntegerOutOfRange(value.to_string())),
+			}
+		}
+	}
+
+	fn serialize_u8(self, value: u8) -> Result<Value, Error> {
+		self.serialize_i64(value.into())
+	}
+
+	fn serialize_u16(self, value: u16) -> Result<Value, Error> {
+		self.serialize_i64(value.into())
+	}
+
+	fn serialize_u32(self, value: u32) -> Result<Value, Error> {
+		self.serialize_i64(value.into())
+	}
+
+	fn serialize_u64(self, value: u64) -> Result<Value, Error> {
+		match i64::try_from(value) {
+			Ok(value) => self.serialize_i64(value),
+			Err(_) => Err(Error::IntegerOutOfRange(value.to_string())),
+		}
+	}
+
+	serde_if_integer128! {
+		fn serialize_u128(self, value: u128) -> Result<Value, Error> {
+			match i64::try_from(value) {
+				Ok(value) => self.serialize_i64(value),
+				Err(_) => Err(Error::IntegerOutOfRange(value.to_string())),
+			}
+		}
+	}
+
+	fn serialize_f32(self, value: f32) -> Result<Value, Error> {
+		if !value.is_finite() {
+			return Err(Error::NonFiniteFloat(value.into()))
+		}
+		// the shortest decimal that reads back as `value`, like `to_query_args` writes, widening
+		// would turn `0.1` into `0.10000000149011612`.
+		Ok(Value::Float(value.to_string().parse().expect("a float is a valid f64")))
+	}
+
+	fn serialize_f64(self, value: f64) -> Result<Value, Error> {
+		if !value.is_finite() {
+			return Err(Error::NonFiniteFloat(value))
+		}
+		Ok(Value::Float(value))
+	}
+
+	fn serialize_char(self, value: char) -> Result<Value, Error> {
+		Ok(Value::String(value.to_string()))
+	}
+
+	fn serialize_str(self, value: &str) -> Result<Value, Error> {
+		Ok(Value::String(value.to_owned()))
+	}
+
+	/// Prisma's `Bytes` are base64 strings.
+	fn serialize_bytes(self, value: &[u8]) -> Result<Value, Error> {
+		Ok(Value::String(base64::encode(value)))
+	}
+
+	fn serialize_none(self) -> Result<Value, Error> {
+		Ok(Value::Null)
+	}
+
+	fn serialize_some<T>(self, value: &T) -> Result<Value, Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> Result<Value, Error> {
+		Ok(Value::Null)
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+		Ok(Value::Null)
+	}
+
+	/// Enums are graphql enum values.
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Value, Error> {
+		if !is_name(variant) {
+			return Err(Error::InvalidEnumValue(variant))
+		}
+		Ok(Value::Enum(variant.to_owned()))
+	}
+
+	fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value, Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T>(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		name: &'static str,
+		value: &T,
+	) -> Result<Value, Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(variant(name, value.serialize(self)?))
+	}
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, Error> {
+		Ok(SerializeList { variant: None, values: Vec::with_capacity(len.unwrap_or_default()) })
+	}
+
+	fn serialize_tuple(self, len: usize) -> Result<SerializeList, Error> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		len: usize,
+	) -> Result<SerializeList, Error> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		len: usize,
+	) -> Result<SerializeList, Error> {
+		Ok(SerializeList { variant: Some(variant), values: Vec::with_capacity(len) })
+	}
+
+	fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject, Error> {
+		Ok(SerializeObject { variant: None, fields: BTreeMap::new(), key: None })
+	}
+
+	fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject, Error> {
+		self.serialize_map(Some(len))
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		_len: usize,
+	) -> Result<SerializeObject, Error> {
+		Ok(SerializeObject { variant: Some(variant), fields: BTreeMap::new(), key: None })
+	}
+}
+
+struct SerializeList {
+	variant: Option<&'static str>,
+	values: Vec<Value>,
+}
+
+impl SerializeList {
+	fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+		self.values.push(value.serialize(ValueSerializer)?);
+		Ok(())
+	}
+
+	fn finish(self) -> Value {
+		match self.variant {
+			Some(name) => variant(name, Value::List(self.values)),
+			None => Value::List(self.values),
+		}
+	}
+}
+
+impl ser::SerializeSeq for SerializeList {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(self.finish())
+	}
+}
+
+impl ser::SerializeTuple for SerializeList {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(self.finish())
+	}
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(self.finish())
+	}
+}
+
+impl ser::SerializeTupleVariant for SerializeList {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(self.finish())
+	}
+}
+
+struct SerializeObject {
+	variant: Option<&'static str>,
+	fields: BTreeMap<String, Value>,
+	key: Option<String>,
+}
+
+impl SerializeObject {
+	fn insert<T: ?Sized + Serialize>(&mut self, key: String, value: &T) -> Result<(), Error> {
+		self.fields.insert(key, value.serialize(ValueSerializer)?);
+		Ok(())
+	}
+
+	fn finish(self) -> Value {
+		match self.variant {
+			Some(name) => variant(name, Value::Object(self.fields)),
+			None => Value::Object(self.fields),
+		}
+	}
+}
+
+impl ser::SerializeMap for SerializeObject {
+	type Ok = Value;
+	type Error = Error;
+
+	/// Object keys are names, strings, enum values and integers are written as they are.
+	fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+		let key = match key.serialize(ValueSerializer)? {
+			Value::String(key) | Value::Enum(key) => key,
+			Value::Int(key) =>
+				key.as_i64().map(|key| key.to_string()).ok_or(Error::KeyMustBeAString)?,
+			_ => return Err(Error::KeyMustBeAString),
+		};
+		self.key = Some(key);
+		Ok(())
+	}
+
+	fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+		let key = self.key.take().expect("serialize_value is called after serialize_key");
+		self.insert(key, value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(self.finish())
+	}
+}
+
+impl ser::SerializeStruct for SerializeObject {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		self.insert(key.to_owned(), value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(self.finish())
+	}
+}
+
+impl ser::SerializeStructVariant for SerializeObject {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		self.insert(key.to_owned(), value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(self.finish())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(query: &'static str) -> Document {
+		parse_query(query).unwrap()
+	}
+
+	#[test]
+	fn binds_variables() {
+		let mut variables = Variables::new();
+		variables.insert("where", serde_json::json!({ "name": "$take: ()" })).unwrap();
+		variables.insert("orderBy", vec![1, 2]).unwrap();
+
+		let document = parse(
+			"query($where: UserWhereInput, $take: Int, $orderBy: [Int!]) { findManyUser(where: \
+			 $where, take: $take, orderBy: $orderBy) { id } }",
+		);
+		assert_eq!(
+			variables.bind(&document).to_string(),
+			parse(
+				r#"query { findManyUser(where: { name: "$take: ()" }, orderBy: [1, 2]) { id } }"#
+			)
+			.to_string(),
+		);
+	}
+
+	#[test]
+	fn drops_empty_arguments() {
+		let document =
+			parse("mutation($where: PostWhereInput) { deleteManyPost(where: $where) { count } }");
+		assert_eq!(
+			Variables::new().bind(&document).to_string(),
+			parse("mutation { deleteManyPost { count } }").to_string(),
+		);
+	}
+
+	#[test]
+	fn serializes_values() {
+		#[derive(Serialize)]
+		enum SortOrder {
+			Asc,
+		}
+
+		let mut variables = Variables::new();
+		variables
+			.insert("v", serde_json::json!({ "in": [1, i32::MIN], "title": null }))
+			.unwrap();
+		assert_eq!(
+			variables.bind(&parse("{ f(v: $v) }")).to_string(),
+			parse("{ f(v: { in: [1, -2147483648], title: null }) }").to_string(),
+		);
+
+		assert_eq!(to_value(SortOrder::Asc).unwrap(), Value::Enum("Asc".to_owned()));
+		assert_eq!(to_value(0.1f32).unwrap(), Value::Float(0.1));
+		assert!(matches!(to_value(f64::NAN), Err(Error::NonFiniteFloat(_))));
+		assert!(matches!(to_value(u64::MAX), Err(Error::IntegerOutOfRange(_))));
+		assert!(matches!(to_value(i64::from(i32::MAX) + 1), Err(Error::IntegerOutOfRange(_))));
+	}
+
+	#[test]
+	fn caches_documents() {
+		let documents = Documents::new();
+		let query = || "query { findManyUser { id } }".to_owned();
+		let first = documents.get_or_parse("{ id }", query).unwrap();
+		let second = documents.get_or_parse("{ id }", || unreachable!()).unwrap();
+		assert!(std::ptr::eq(first, second));
+		assert!(documents.get_or_parse("{ name }", || "query {".to_owned()).is_err());
+	}
+}